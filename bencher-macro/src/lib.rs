@@ -17,7 +17,27 @@ struct Args {
     no_test: Option<()>,
 
     #[darling(default)]
-    bytes: Option<()>
+    bytes: Option<()>,
+
+    /// `elements`: print throughput as elements/sec (`b.elements` per
+    /// iteration) instead of, or alongside, bytes/sec.
+    #[darling(default)]
+    elements: Option<()>,
+
+    /// `params = [16, 256, 4096, 65536]`: run the bench once per value,
+    /// with each run's label suffixed `<name>/<value>` (mirroring
+    /// criterion's `BenchmarkId`) and its own `Step`-series and `finish()`
+    /// line. The element type is whatever the list literal infers to, so
+    /// arbitrary `Display` types work, not just integers.
+    #[darling(default)]
+    params: Option<syn::Expr>,
+
+    /// `regression = 5`: allow at most a 5% slowdown in median time versus
+    /// `bench_rs::DEFAULT_BASELINE_PATH`; a run that exceeds it panics,
+    /// letting CI fail on performance regressions. An integer percentage
+    /// (not `5.0`), so it parses as a plain int literal.
+    #[darling(default)]
+    regression: Option<u32>,
 }
 
 #[proc_macro_attribute]
@@ -32,6 +52,7 @@ pub fn bench(attrs: proc_macro::TokenStream, item: proc_macro::TokenStream) -> p
     let name = args.name.map(|s| s.to_token_stream()).unwrap_or(func_name.to_string().to_token_stream());
     let count = args.count.unwrap_or(1000).to_token_stream();
     let display_bytes = args.bytes.is_some();
+    let display_elements = args.elements.is_some();
     let test = if args.no_test.is_some() {
         TokenStream::new()
     } else {
@@ -40,11 +61,41 @@ pub fn bench(attrs: proc_macro::TokenStream, item: proc_macro::TokenStream) -> p
 
     let bencher = if cfg!(feature = "track-allocator") {
         quote! {
-            Bencher::new(#name, #count, 0, #display_bytes, bench_rs::GLOBAL_ALLOC)
+            Bencher::new(&bench_name, #count, 0, #display_bytes, bench_rs::GLOBAL_ALLOC)
+        }
+    } else {
+        quote! {
+            Bencher::new(&bench_name, #count, 0, #display_bytes, GLOBAL_ALLOC)
+        }
+    };
+
+    let regression_setup = args.regression.map(|regression| {
+        quote! {
+            bencher.compare_baseline(bench_rs::DEFAULT_BASELINE_PATH);
+            bencher.regression_threshold = Some(#regression as f64);
+        }
+    });
+
+    let body = if let Some(params) = args.params {
+        quote! {
+            for current_param in #params {
+                let bench_name = format!("{}/{}", #name, current_param);
+                let mut bencher = #bencher;
+                bencher.display_elements = #display_elements;
+                bencher.param = current_param;
+                #regression_setup
+                #func_name(&mut bencher);
+                bencher.finish();
+            }
         }
     } else {
         quote! {
-            Bencher::new(#name, #count, 0, #display_bytes, GLOBAL_ALLOC)
+            let bench_name = #name.to_string();
+            let mut bencher = #bencher;
+            bencher.display_elements = #display_elements;
+            #regression_setup
+            #func_name(&mut bencher);
+            bencher.finish();
         }
     };
 
@@ -54,9 +105,7 @@ pub fn bench(attrs: proc_macro::TokenStream, item: proc_macro::TokenStream) -> p
         fn #func_name() {
             #func
 
-            let mut bencher = #bencher;
-            #func_name(&mut bencher);
-            bencher.finish();
+            #body
         }
     }).into()
 }
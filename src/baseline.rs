@@ -0,0 +1,51 @@
+use crate::Stats;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Conventional location for the regression baseline when a `#[bench]`
+/// only sets `regression = N` without an explicit `compare_baseline` path.
+pub const DEFAULT_BASELINE_PATH: &str = "bench_baseline.json";
+
+pub(crate) fn load(path: impl AsRef<Path>) -> HashMap<String, Stats> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub(crate) fn save(path: impl AsRef<Path>, name: &str, stats: &Stats) {
+    let mut all = load(&path);
+    all.insert(name.to_owned(), stats.clone());
+    if let Ok(json) = serde_json::to_string_pretty(&all) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+pub(crate) fn percent_change(current: usize, baseline: usize) -> f64 {
+    if baseline == 0 {
+        return 0.0;
+    }
+    (current as f64 - baseline as f64) / baseline as f64 * 100.0
+}
+
+/// Prints the percentage change in median time, memory and allocations
+/// against `baseline`, in red for regressions and green for improvements.
+pub(crate) fn print_diff(current: &Stats, baseline: &Stats) {
+    bunt::print!(" vs baseline time ");
+    print_change(percent_change(current.times_median, baseline.times_median));
+    bunt::print!(", mem ");
+    print_change(percent_change(current.mem_max, baseline.mem_max));
+    bunt::print!(", alloc ");
+    print_change(percent_change(current.allocations, baseline.allocations));
+}
+
+fn print_change(pct: f64) {
+    let text = format!("{pct:+.1}%");
+    if pct > 0.0 {
+        bunt::print!("{$red}{}{/$}", text);
+    } else if pct < 0.0 {
+        bunt::print!("{$green}{}{/$}", text);
+    } else {
+        bunt::print!("{}", text);
+    }
+}
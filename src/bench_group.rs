@@ -0,0 +1,79 @@
+use crate::bencher::{format_duration, format_name, format_ops};
+use crate::{Bencher, Stats};
+use human_bytes::human_bytes;
+use stats_alloc::StatsAlloc;
+
+struct GroupRow {
+    name: String,
+    stats: Stats,
+    bytes: usize,
+}
+
+/// Collects several `Bencher` runs under a shared title and prints one
+/// aligned table when the group closes, instead of a `default_format` line
+/// per bench. Meant for comparing competing implementations of the same
+/// task, e.g. via the `bench_group!` macro.
+pub struct BenchGroup<A: std::alloc::GlobalAlloc + 'static> {
+    title: String,
+    allocator: &'static StatsAlloc<A>,
+    rows: Vec<GroupRow>,
+}
+
+impl<A: std::alloc::GlobalAlloc> BenchGroup<A> {
+    pub fn new(title: impl Into<String>, allocator: &'static StatsAlloc<A>) -> Self {
+        BenchGroup {
+            title: title.into(),
+            allocator,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Runs `f` through its own `Bencher` and stashes its `Stats` for the
+    /// table printed by `finish()`. `f` may assign `b.bytes` (same as a
+    /// plain `#[bench(bytes)]`) to get a throughput column in the table.
+    pub fn bench(&mut self, name: impl AsRef<str>, count: usize, mut f: impl FnMut(&mut Bencher<A>)) {
+        let mut bencher = Bencher::new(name.as_ref(), count, 0, false, self.allocator);
+        f(&mut bencher);
+        self.rows.push(GroupRow {
+            name: name.as_ref().to_owned(),
+            stats: Stats::from(bencher.steps.as_slice()),
+            bytes: bencher.bytes,
+        });
+    }
+
+    pub fn finish(self) {
+        let fastest = self
+            .rows
+            .iter()
+            .map(|row| row.stats.times_median)
+            .min()
+            .unwrap_or(1)
+            .max(1);
+
+        bunt::println!("{$white+bold}{}{/$}", self.title);
+        for row in &self.rows {
+            let relative = row.stats.times_median as f64 / fastest as f64;
+            let relative_str = if relative <= 1.0 {
+                "1.00x".to_string()
+            } else {
+                format!("{relative:.2}x slower")
+            };
+
+            bunt::print!(
+                "{[white+bold]:>30} ... {[green]:>9} {$cyan}{:>5} op/s{/$}",
+                format_name(&row.name),
+                format_duration(row.stats.times_median, row.stats.times_median, false),
+                format_ops(1_000_000_000 / row.stats.times_median.max(1), true)
+            );
+
+            if row.bytes != 0 {
+                let bytes_str = human_bytes(
+                    row.bytes as f64 * (1_000_000_000f64 / row.stats.times_median.max(1) as f64),
+                );
+                bunt::print!(", {$cyan}{:>8}/s{/$}", bytes_str);
+            }
+
+            bunt::println!(" {[yellow]}", relative_str);
+        }
+    }
+}
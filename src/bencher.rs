@@ -1,9 +1,10 @@
-use crate::{timing_future::TimingFuture, Stats, Step};
+use crate::{baseline, timing_future::TimingFuture, Stats, Step};
 use human_bytes::human_bytes;
 use lazy_static::lazy_static;
 use stats_alloc::{Region, StatsAlloc};
 use std::future::Future;
 use std::hint::black_box;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 
@@ -15,11 +16,16 @@ const MAX_NAME_LEN: usize = 30;
 const DEFAULT_DEADLINE_MS: u64 = 300;
 const MAX_ITERATIONS: usize = 2000;
 
-pub struct Bencher<A: std::alloc::GlobalAlloc + 'static> {
+pub struct Bencher<A: std::alloc::GlobalAlloc + 'static, P = ()> {
     pub name: String,
     pub count: usize,
     pub steps: Vec<Step>,
     pub bytes: usize,
+    /// Number of elements processed per iteration, for workloads where
+    /// elements/sec is the meaningful rate rather than bytes/sec (e.g.
+    /// deduplicating a `Vec<T>`). `elements` value should be assigned from
+    /// the bench.
+    pub elements: usize,
     pub n: usize,
     pub poll: usize,
     /// Number of performed iterations.
@@ -27,14 +33,31 @@ pub struct Bencher<A: std::alloc::GlobalAlloc + 'static> {
     /// Whenever to display throughput stats. `bytes` value should be assigned
     /// from the bench.
     pub display_bytes: bool,
-    pub format_fn: fn(&Stats, &Bencher<A>),
+    /// Whenever to display elements/sec. `elements` value should be assigned
+    /// from the bench.
+    pub display_elements: bool,
+    /// Current value of a `#[bench(params = [..])]` sweep. Unit `()` for
+    /// benches that aren't parameterized.
+    pub param: P,
+    pub format_fn: fn(&Stats, &Bencher<A, P>),
+    /// Whenever `format_fn` is the NDJSON formatter. Lets `finish()` keep
+    /// the baseline diff off the machine-readable stream instead of
+    /// comparing `format_fn` by function-pointer identity.
+    json_output: bool,
     allocator: &'static StatsAlloc<A>,
 
+    baseline_save_path: Option<PathBuf>,
+    baseline_compare_path: Option<PathBuf>,
+    /// Allowed slowdown in median time, as a percentage, before `finish()`
+    /// panics. Only takes effect once `compare_baseline` has a prior entry
+    /// to compare against. Set via `#[bench(regression = ..)]`.
+    pub regression_threshold: Option<f64>,
+
     // time, mem, allocations, leaked
     pub mem_track: (AtomicUsize, AtomicUsize, AtomicUsize, AtomicUsize),
 }
 
-impl<A: std::alloc::GlobalAlloc> Bencher<A> {
+impl<A: std::alloc::GlobalAlloc, P: Default> Bencher<A, P> {
     pub fn new(
         name: impl AsRef<str>,
         count: usize,
@@ -47,13 +70,25 @@ impl<A: std::alloc::GlobalAlloc> Bencher<A> {
             count,
             steps: Vec::with_capacity(count),
             bytes,
+            elements: 0,
             n: 0,
             poll: 0,
             passed: 0,
             display_bytes,
-            format_fn: |s, b| Self::default_format(s, b),
+            display_elements: false,
+            param: P::default(),
+            format_fn: if Self::json_format_enabled() {
+                |s, b| Self::json_format(s, b)
+            } else {
+                |s, b| Self::default_format(s, b)
+            },
+            json_output: Self::json_format_enabled(),
             allocator,
 
+            baseline_save_path: None,
+            baseline_compare_path: None,
+            regression_threshold: None,
+
             mem_track: (
                 AtomicUsize::new(0),
                 AtomicUsize::new(0),
@@ -63,6 +98,38 @@ impl<A: std::alloc::GlobalAlloc> Bencher<A> {
         }
     }
 
+    /// Current value of the parameter sweep (see `#[bench(params = [..])]`).
+    pub fn param(&self) -> &P {
+        &self.param
+    }
+
+    /// Write this bench's finished `Stats`, keyed by name, into the JSON
+    /// file at `path` once `finish()` runs. Ignored when
+    /// `regression_threshold` is set, since a gated run must never
+    /// overwrite the committed baseline it's being checked against.
+    pub fn save_baseline(&mut self, path: impl Into<PathBuf>) {
+        self.baseline_save_path = Some(path.into());
+    }
+
+    /// Load a previously saved baseline entry for this bench's name and, in
+    /// `finish()`, print the percentage change in median time, memory and
+    /// allocations against it.
+    pub fn compare_baseline(&mut self, path: impl Into<PathBuf>) {
+        self.baseline_compare_path = Some(path.into());
+    }
+
+    /// Emit one NDJSON record per `finish()` instead of the colored
+    /// `default_format` line. Set automatically when `BENCH_FORMAT=json` is
+    /// in the environment; call this to opt in without the env var.
+    pub fn use_json_format(&mut self) {
+        self.format_fn = |s, b| Self::json_format(s, b);
+        self.json_output = true;
+    }
+
+    fn json_format_enabled() -> bool {
+        std::env::var("BENCH_FORMAT").as_deref() == Ok("json")
+    }
+
     // (time, memory_usage, passed)
     #[inline]
     pub fn bench_once<T>(
@@ -211,7 +278,49 @@ impl<A: std::alloc::GlobalAlloc> Bencher<A> {
 
     pub fn finish(&self) {
         let stats = Stats::from(self.steps.as_slice());
-        (self.format_fn)(&stats, self)
+
+        let comparison = self
+            .baseline_compare_path
+            .as_ref()
+            .and_then(|path| baseline::load(path).get(&self.name).cloned());
+
+        (self.format_fn)(&stats, self);
+
+        // The colored diff is for humans; printing it on top of an NDJSON
+        // stream would corrupt it, so JSON mode skips it entirely.
+        if !self.json_output {
+            if let Some(previous) = &comparison {
+                baseline::print_diff(&stats, previous);
+                println!();
+            }
+        }
+
+        // A regression gate must compare against a fixed, previously
+        // committed baseline, not one the gated run keeps overwriting with
+        // itself — that would let a steady per-run drift never trip the
+        // threshold. So a run that's gating never auto-saves; updating the
+        // committed baseline is a separate, deliberate step.
+        if self.regression_threshold.is_none() {
+            if let Some(path) = &self.baseline_save_path {
+                baseline::save(path, &self.name, &stats);
+            }
+        }
+
+        if self.regression_threshold.is_some() && comparison.is_none() {
+            eprintln!(
+                "{}: regression threshold set but no baseline entry found for this name \u{2014} gate is not checking anything",
+                self.name
+            );
+        }
+
+        if let (Some(previous), Some(threshold)) = (&comparison, self.regression_threshold) {
+            let change = baseline::percent_change(stats.times_median, previous.times_median);
+            assert!(
+                change <= threshold,
+                "{}: {change:+.1}% slower than baseline, exceeds allowed {threshold:.1}%",
+                self.name
+            );
+        }
     }
 
     pub fn reset_mem(&self) {
@@ -229,7 +338,7 @@ impl<A: std::alloc::GlobalAlloc> Bencher<A> {
         )
     }
 
-    fn default_format(stats: &Stats, bencher: &Bencher<A>) {
+    fn default_format(stats: &Stats, bencher: &Bencher<A, P>) {
         let first = if FIRST.swap(false, Ordering::SeqCst) {
             "."
         } else {
@@ -241,27 +350,35 @@ impl<A: std::alloc::GlobalAlloc> Bencher<A> {
             first,
             format_name(&bencher.name),
             format_duration(
-                stats.times_average,
-                stats.times_average,
+                stats.times_median,
+                stats.times_median,
                 false
             ),
             &["(+/-", &format_duration(
-                    stats.times_max.saturating_sub(stats.times_average).max(stats.times_average.saturating_sub(stats.times_min)),
-                    stats.times_average,
+                    stats.times_std_dev,
+                    stats.times_median,
                     true
                 ), "),"].concat(),
-            format_ops(1_000_000_000 / stats.times_average.max(1), true)
+            format_ops(1_000_000_000 / stats.times_median.max(1), true)
         );
         if bencher.display_bytes {
             if bencher.bytes != 0 {
                 let bytes_str = human_bytes(
-                    bencher.bytes as f64 * (1_000_000_000f64 / stats.times_average.max(1) as f64),
+                    bencher.bytes as f64 * (1_000_000_000f64 / stats.times_median.max(1) as f64),
                 );
                 bunt::print!(", {$cyan}{:>8}/s{/$}", bytes_str);
             } else {
                 bunt::print!(", {$cyan+dimmed}     0 B/s{/$}");
             }
         }
+        if bencher.display_elements {
+            let elements_per_sec =
+                (bencher.elements as f64 * 1_000_000_000f64 / stats.times_median.max(1) as f64) as usize;
+            bunt::print!(
+                ", {$cyan}{:>8} elem/s{/$}",
+                format_ops(elements_per_sec, true)
+            );
+        }
 
         bunt::print!(", 🐏 ");
         let memory_str = human_bytes(stats.mem_max as f64);
@@ -302,9 +419,45 @@ impl<A: std::alloc::GlobalAlloc> Bencher<A> {
             bunt::println!(" ▶ {[magenta]}", format_ops(bencher.passed, true));
         }
     }
+
+    fn json_format(stats: &Stats, bencher: &Bencher<A, P>) {
+        let bytes_per_sec = (bencher.display_bytes && bencher.bytes != 0).then(|| {
+            bencher.bytes as f64 * 1_000_000_000f64 / stats.times_median.max(1) as f64
+        });
+        let elements_per_sec = bencher.display_elements.then(|| {
+            bencher.elements as f64 * 1_000_000_000f64 / stats.times_median.max(1) as f64
+        });
+
+        let record = JsonRecord {
+            name: &bencher.name,
+            stats,
+            passed: bencher.passed,
+            poll: bencher.poll,
+            bytes_per_sec,
+            elements_per_sec,
+        };
+
+        if let Ok(line) = serde_json::to_string(&record) {
+            println!("{line}");
+        }
+    }
+}
+
+/// One NDJSON line per finished benchmark, for `BENCH_FORMAT=json` /
+/// `Bencher::use_json_format`. Since `Stats` already derives `Serialize`,
+/// this is mostly a wrapper adding the name, iteration count and
+/// throughput figures around it.
+#[derive(serde::Serialize)]
+struct JsonRecord<'a> {
+    name: &'a str,
+    stats: &'a Stats,
+    passed: usize,
+    poll: usize,
+    bytes_per_sec: Option<f64>,
+    elements_per_sec: Option<f64>,
 }
 
-fn format_name(s: &str) -> String {
+pub(crate) fn format_name(s: &str) -> String {
     let mut s = s.strip_prefix("bench_").unwrap_or(s);
     s = s.strip_prefix("test_").unwrap_or(s);
     if s.len() > MAX_NAME_LEN {
@@ -319,7 +472,7 @@ fn format_name(s: &str) -> String {
     }
 }
 
-fn format_ops(value: usize, with_unit: bool) -> String {
+pub(crate) fn format_ops(value: usize, with_unit: bool) -> String {
     if value < 1_000 {
         let unit = "";
         format!("{value}{unit}")
@@ -335,7 +488,7 @@ fn format_ops(value: usize, with_unit: bool) -> String {
     }
 }
 
-fn format_duration(value: usize, mean: usize, short: bool) -> String {
+pub(crate) fn format_duration(value: usize, mean: usize, short: bool) -> String {
     if mean < 1_000 {
         format!("{value} ns")
     } else if mean < 1_000_000 {
@@ -1,17 +1,54 @@
 use serde::{Deserialize, Serialize};
 
+pub use baseline::DEFAULT_BASELINE_PATH;
+pub use bench_group::BenchGroup;
 pub use bencher::Bencher;
 pub use bencher_macro::*;
 
+mod baseline;
+mod bench_group;
 mod bencher;
 mod timing_future;
 mod track_allocator;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Benchmarks several competing implementations of the same task under a
+/// shared title and prints one aligned table comparing them, rather than a
+/// `default_format` line per bench:
+///
+/// ```ignore
+/// bench_group!("encoders", {
+///     "rcnb" => |b| { b.iter(|| rcnb_rs::encode(&data)); },
+///     "base64" => |b| { b.iter(|| base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&data)); },
+/// });
+/// ```
+#[macro_export]
+macro_rules! bench_group {
+    ($title:expr, { $($name:expr => $body:expr),+ $(,)? }) => {{
+        let mut group = $crate::BenchGroup::new($title, $crate::GLOBAL_ALLOC);
+        $(group.bench($name, 1000, $body);)+
+        group.finish();
+    }};
+}
+
+// Samples below the 5th percentile or above the 95th are clamped to those
+// percentiles before any other statistic is computed, so a couple of
+// OS-scheduling spikes can't blow up the reported deviation.
+const WINSORIZE_LOW_PCT: f64 = 5.0;
+const WINSORIZE_HIGH_PCT: f64 = 95.0;
+// Scales the median absolute deviation so it estimates the standard
+// deviation of a normal distribution (1 / Phi^-1(3/4)).
+const MAD_SCALE: f64 = 1.4826;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Stats {
-    pub times_average: usize,
-    pub times_min: usize,
-    pub times_max: usize,
+    pub times_mean: usize,
+    pub times_median: usize,
+    pub times_variance: f64,
+    pub times_std_dev: usize,
+    pub times_q1: usize,
+    pub times_q3: usize,
+    pub times_iqr: usize,
+    pub times_mad: usize,
 
     pub mem_average: usize,
     pub mem_min: usize,
@@ -29,12 +66,61 @@ pub struct Step {
     leaked_bytes: usize,
 }
 
-impl From<&Vec<Step>> for Stats {
-    fn from(steps: &Vec<Step>) -> Self {
+// Linear-interpolated percentile (`rank = p/100 * (n - 1)`), interpolating
+// between the floor and ceil ranks. `sorted` must already be sorted.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        n => {
+            let rank = p / 100.0 * (n - 1) as f64;
+            let lo = rank.floor() as usize;
+            let hi = rank.ceil() as usize;
+            if lo == hi {
+                sorted[lo]
+            } else {
+                sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64)
+            }
+        }
+    }
+}
+
+fn winsorize(values: &mut [f64], low: f64, high: f64) {
+    for value in values.iter_mut() {
+        if *value < low {
+            *value = low;
+        } else if *value > high {
+            *value = high;
+        }
+    }
+}
+
+impl From<&[Step]> for Stats {
+    fn from(steps: &[Step]) -> Self {
         let count = steps.len();
 
-        let times = steps.iter().map(|step| step.time).collect::<Vec<u128>>();
-        let times_iter = times.iter();
+        let mut times = steps
+            .iter()
+            .map(|step| step.time as f64)
+            .collect::<Vec<f64>>();
+        times.sort_by(|a, b| a.total_cmp(b));
+
+        let low = percentile(&times, WINSORIZE_LOW_PCT);
+        let high = percentile(&times, WINSORIZE_HIGH_PCT);
+        winsorize(&mut times, low, high);
+
+        let mean = times.iter().sum::<f64>() / count.max(1) as f64;
+        let variance = times.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / count.max(1) as f64;
+        let median = percentile(&times, 50.0);
+        let q1 = percentile(&times, 25.0);
+        let q3 = percentile(&times, 75.0);
+
+        let mut abs_deviations = times
+            .iter()
+            .map(|t| (t - median).abs())
+            .collect::<Vec<f64>>();
+        abs_deviations.sort_by(|a, b| a.total_cmp(b));
+        let mad = percentile(&abs_deviations, 50.0) * MAD_SCALE;
 
         let mem = steps.iter().map(|step| step.mem).collect::<Vec<usize>>();
         let mem_iter = mem.iter();
@@ -52,10 +138,15 @@ impl From<&Vec<Step>> for Stats {
         let leaked_bytes_iter = l_bytes.iter();
 
         Stats {
-            times_average: (times_iter.clone().sum::<u128>() / count as u128) as usize,
-            times_min: times_iter.clone().copied().min().unwrap_or_default() as usize,
-            times_max: times_iter.clone().copied().max().unwrap_or_default() as usize,
-            mem_average: mem_iter.clone().sum::<usize>() / count,
+            times_mean: mean as usize,
+            times_median: median as usize,
+            times_variance: variance,
+            times_std_dev: variance.sqrt() as usize,
+            times_q1: q1 as usize,
+            times_q3: q3 as usize,
+            times_iqr: (q3 - q1) as usize,
+            times_mad: mad as usize,
+            mem_average: mem_iter.clone().sum::<usize>() / count.max(1),
             mem_min: mem_iter.clone().copied().min().unwrap_or_default(),
             mem_max: mem_iter.clone().copied().max().unwrap_or_default(),
             allocations: allocations_iter.clone().copied().max().unwrap_or_default(),
@@ -63,3 +154,44 @@ impl From<&Vec<Step>> for Stats {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(time: u128) -> Step {
+        Step {
+            time,
+            mem: 0,
+            allocations: 0,
+            leaked_bytes: 0,
+        }
+    }
+
+    // Pins the winsorized percentile math against a known sample: times
+    // 10..=100 in steps of 10, where the 5th/95th percentile winsorizing
+    // clamps 10 up to 14.5 and 100 down to 95.5 before anything else is
+    // computed.
+    #[test]
+    fn stats_from_pins_percentiles_and_winsorizing() {
+        let steps = (1..=10).map(|i| step(i * 10)).collect::<Vec<Step>>();
+
+        let stats = Stats::from(steps.as_slice());
+
+        assert_eq!(stats.times_mean, 55);
+        assert_eq!(stats.times_median, 55);
+        assert_eq!(stats.times_q1, 32);
+        assert_eq!(stats.times_q3, 77);
+        assert_eq!(stats.times_iqr, 45);
+        assert_eq!(stats.times_std_dev, 27);
+        assert_eq!(stats.times_mad, 37);
+    }
+
+    #[test]
+    fn stats_from_empty_does_not_panic() {
+        let stats = Stats::from(&[] as &[Step]);
+
+        assert_eq!(stats.times_median, 0);
+        assert_eq!(stats.mem_average, 0);
+    }
+}
@@ -8,4 +8,4 @@ use std::alloc::System;
 #[global_allocator]
 pub static GLOBAL_ALLOC: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
 #[cfg(feature = "track-allocator")]
-pub type Bencher = B<std::alloc::System>;
+pub type Bencher<P = ()> = B<std::alloc::System, P>;
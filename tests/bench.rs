@@ -89,3 +89,98 @@ fn bench_base64(b: &mut Bencher) {
 fn bench_no_run(_: &mut Bencher) {
     println!("no #[test]");
 }
+
+// Gated against the committed `bench_rs::DEFAULT_BASELINE_PATH`. Updating
+// that baseline is a separate, deliberate step (see `bench_rcnb_baseline`
+// below) — a gated run never writes to it itself. Both benches share the
+// `name`, since compare_baseline/save_baseline key their entry by it, not
+// by the function name.
+#[bench(name = "rcnb_gated", regression = 5)]
+fn bench_rcnb_gated(b: &mut Bencher) {
+    let data = rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(1000)
+        .collect::<Vec<u8>>();
+    b.iter(|| {
+        let _ = rcnb_rs::encode(&data);
+    });
+}
+
+// Run manually (e.g. `cargo test bench_rcnb_baseline -- --ignored`) to
+// refresh the committed baseline after an intentional perf change.
+#[bench(name = "rcnb_gated")]
+#[ignore]
+fn bench_rcnb_baseline(b: &mut Bencher) {
+    let data = rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(1000)
+        .collect::<Vec<u8>>();
+    b.iter(|| {
+        let _ = rcnb_rs::encode(&data);
+    });
+    b.save_baseline(bench_rs::DEFAULT_BASELINE_PATH);
+}
+
+#[bench(elements)]
+fn bench_dedup(b: &mut Bencher) {
+    let mut data = (0..1000).map(|i| i % 100).collect::<Vec<i32>>();
+    b.elements = data.len();
+    b.iter(|| {
+        let mut data = data.clone();
+        data.sort_unstable();
+        data.dedup();
+        data
+    });
+}
+
+#[test]
+fn test_bench_group() {
+    let data = rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(1000)
+        .collect::<Vec<u8>>();
+
+    bench_rs::bench_group!("encoders", {
+        "rcnb" => |b| {
+            b.iter(|| { let _ = rcnb_rs::encode(&data); });
+            b.bytes = data.len();
+        },
+        "base64" => |b| {
+            b.iter(|| { let _ = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&data); });
+            b.bytes = data.len();
+        },
+    });
+}
+
+#[test]
+fn test_json_format() {
+    let data = rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(1000)
+        .collect::<Vec<u8>>();
+
+    let mut bencher = Bencher::new(
+        "test_json_format",
+        100,
+        data.len(),
+        true,
+        bench_rs::GLOBAL_ALLOC,
+    );
+    bencher.use_json_format();
+    bencher.iter(|| {
+        let _ = rcnb_rs::encode(&data);
+    });
+    bencher.finish();
+}
+
+#[bench(name = "encode", params = [16, 256, 4096, 65536], bytes)]
+fn bench_rcnb_sized(b: &mut Bencher<usize>) {
+    let data = rand::rng()
+        .sample_iter(rand::distr::Alphanumeric)
+        .take(*b.param())
+        .collect::<Vec<u8>>();
+    b.iter(|| {
+        let _ = rcnb_rs::encode(&data);
+    });
+    b.bytes = data.len()
+}